@@ -49,7 +49,7 @@ impl<Metadata> PrefixMatch<Metadata> {
     pub fn find(&self, prefix: &str) -> Match {
         // This works as cmp() will return Ordering::Equal if the prefix matches.
         let Ok(mid) = self.items.binary_search_by(|item| find_cmp(&item.0, prefix)) else {
-            return Match { range: 0..0 };
+            return Match { range: 0..0, prefix_len: prefix.len() };
         };
 
         // Look left and try to find more matching prefixes
@@ -64,13 +64,167 @@ impl<Metadata> PrefixMatch<Metadata> {
             end += 1;
         }
 
-        Match { range: start..end + 1 }
+        Match { range: start..end + 1, prefix_len: prefix.len() }
     }
+
+    /// Find items whose prefix is within `max_distance` edits of `prefix`, so a
+    /// search for `fiel` still matches `file`. Matching is driven by a bounded
+    /// Levenshtein automaton: each state is the set of reachable
+    /// (query position, errors so far) pairs after consuming some candidate
+    /// prefix, represented as the row of the edit-distance transition table for
+    /// that prefix. A candidate is accepted as soon as the automaton reaches
+    /// the end of the query with errors <= `max_distance`, since we're matching
+    /// a prefix and the candidate itself doesn't need to end there.
+    ///
+    /// Must call [`PrefixMatch::reorder`] before calling this.
+    pub fn find_fuzzy(&self, prefix: &str, max_distance: u8) -> FuzzyMatch {
+        let query: Vec<char> = prefix.chars().collect();
+        let Some(&first) = query.first() else {
+            return FuzzyMatch { matches: Vec::new() };
+        };
+
+        let (lo, hi) = self.first_char_bounds(first, max_distance);
+        let mut matches = Vec::new();
+        for (i, (s, _)) in self.items[lo..hi].iter().enumerate() {
+            if let Some(distance) = fuzzy_prefix_distance(s, &query, max_distance) {
+                matches.push((lo + i, distance));
+            }
+        }
+
+        FuzzyMatch { matches }
+    }
+
+    /// Narrows the binary search to the widest contiguous slice of `items`
+    /// (sorted by [`sort_cmp`]) whose first character could possibly be within
+    /// `max_distance` edits of `first`. This only narrows anything when
+    /// `max_distance == 0`: once a single substitution is allowed, any first
+    /// character is reachable, so there's nothing to prune.
+    fn first_char_bounds(&self, first: char, max_distance: u8) -> (usize, usize) {
+        if max_distance > 0 {
+            return (0, self.items.len());
+        }
+
+        let lo = self.items.partition_point(|item| item.0.chars().next().is_none_or(|c| c < first));
+        let hi = self.items.partition_point(|item| item.0.chars().next().is_none_or(|c| c <= first));
+        (lo, hi)
+    }
+
+    /// Score-ranked subsequence match: `pattern` is matched case-insensitively
+    /// as an ordered subsequence of each item (characters appear in order, but
+    /// need not be contiguous), e.g. `fnm` matches `file_name`. This lets
+    /// callers drive fuzzy file/symbol pickers where users type sparse
+    /// abbreviations.
+    ///
+    /// Matches are scored additively: a bonus per matched character, an extra
+    /// bonus for each matched character that directly follows the previous
+    /// matched character, a penalty
+    /// proportional to the candidate's length, and a penalty proportional to
+    /// the span between the first and last matched character (favoring tight
+    /// matches near the start). Results are sorted by descending score.
+    pub fn rank(&self, pattern: &str) -> Vec<(&str, &Metadata, i32)> {
+        let pattern: Vec<char> = pattern.chars().map(lower).collect();
+
+        let mut results: Vec<_> = self
+            .items
+            .iter()
+            .filter_map(|(s, meta)| rank_score(s, &pattern).map(|score| (s.as_str(), meta, score)))
+            .collect();
+
+        results.sort_unstable_by_key(|&(_, _, score)| std::cmp::Reverse(score));
+        results
+    }
+}
+
+/// Caps the candidate length considered by [`PrefixMatch::rank`], for performance.
+const RANK_MAX_LEN: usize = 1000;
+
+#[inline]
+fn lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Greedily matches `pattern` as an ordered subsequence of `candidate`
+/// (case-insensitively) and scores the match, or returns `None` if `pattern`
+/// isn't a subsequence of `candidate`.
+fn rank_score(candidate: &str, pattern: &[char]) -> Option<i32> {
+    if pattern.is_empty() {
+        return Some(0);
+    }
+
+    const MATCH_BONUS: i32 = 16;
+    const CONTIGUOUS_BONUS: i32 = 8;
+    const LENGTH_PENALTY: i32 = 1;
+    const SPAN_PENALTY: i32 = 1;
+
+    let chars: Vec<char> = candidate.chars().take(RANK_MAX_LEN).map(lower).collect();
+
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut search_from = 0;
+    for &p in pattern {
+        let pos = chars[search_from..].iter().position(|&c| c == p)? + search_from;
+        positions.push(pos);
+        search_from = pos + 1;
+    }
+
+    let first = *positions.first().unwrap();
+    let last = *positions.last().unwrap();
+    let contiguous_runs = positions.windows(2).filter(|w| w[1] == w[0] + 1).count() as i32;
+
+    Some(
+        MATCH_BONUS * pattern.len() as i32 + CONTIGUOUS_BONUS * contiguous_runs
+            - LENGTH_PENALTY * chars.len() as i32
+            - SPAN_PENALTY * (last - first) as i32,
+    )
+}
+
+/// One row of the bounded Levenshtein automaton: `row[j]` is the edit distance
+/// between the candidate prefix consumed so far and `query[..j]`.
+struct LevenshteinState {
+    row: Vec<u8>,
+}
+
+impl LevenshteinState {
+    fn start(query_len: usize) -> Self {
+        LevenshteinState { row: (0..=query_len as u8).collect() }
+    }
+
+    /// Transition this state by consuming one more candidate character.
+    fn step(&self, query: &[char], c: char) -> LevenshteinState {
+        let mut next = Vec::with_capacity(self.row.len());
+        next.push(self.row[0] + 1);
+        for (j, &q) in query.iter().enumerate() {
+            let cost = u8::from(q != c);
+            let sub = self.row[j] + cost;
+            let del = self.row[j + 1] + 1;
+            let ins = next[j] + 1;
+            next.push(sub.min(del).min(ins));
+        }
+        LevenshteinState { row: next }
+    }
+}
+
+/// Feeds `candidate`'s characters into a bounded Levenshtein automaton for
+/// `query` and returns the smallest edit distance seen at the point the query
+/// was fully consumed, if any such point stayed within `max_distance`.
+fn fuzzy_prefix_distance(candidate: &str, query: &[char], max_distance: u8) -> Option<u8> {
+    let mut state = LevenshteinState::start(query.len());
+    let mut best = state.row[query.len()];
+
+    for c in candidate.chars() {
+        state = state.step(query, c);
+        best = best.min(state.row[query.len()]);
+        if *state.row.iter().min().unwrap() > max_distance {
+            break;
+        }
+    }
+
+    (best <= max_distance).then_some(best)
 }
 
 /// Storage mechanism for [`PrefixMatch::find`].
 pub struct Match {
     range: Range<usize>,
+    prefix_len: usize,
 }
 
 impl Match {
@@ -81,6 +235,251 @@ impl Match {
     ) -> impl Iterator<Item = (&'s str, &'s Metadata)> {
         tree.items[self.range.clone()].iter().map(|item| (item.0.as_str(), &item.1))
     }
+
+    /// Iterate through all items that match, together with the byte ranges
+    /// within each string that were matched by the query, so callers can
+    /// bold/underline the matched portion in a UI. For a prefix match this is
+    /// always `0..prefix.len()`, but the `Vec<Range<usize>>` shape is what
+    /// lets non-contiguous matchers (fuzzy/subsequence) report multiple spans.
+    pub fn iter_with_spans<'s, Metadata>(
+        &self,
+        tree: &'s PrefixMatch<Metadata>,
+    ) -> impl Iterator<Item = (&'s str, &'s Metadata, Vec<Range<usize>>)> {
+        let prefix_len = self.prefix_len;
+        tree.items[self.range.clone()].iter().map(move |item| {
+            // A prefix match always has exactly one span.
+            #[allow(clippy::single_range_in_vec_init)]
+            let spans = vec![0..prefix_len];
+            (item.0.as_str(), &item.1, spans)
+        })
+    }
+}
+
+/// Controls how many suffixes [`SuffixMatch::insert`] indexes for a key,
+/// trading memory for the ability to match further into the string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuffixMode {
+    /// Index every suffix of the key, so [`SuffixMatch::find_contains`] can match anywhere.
+    All,
+    /// Index only suffixes that start right after a `::` or `_` delimiter
+    /// (plus the key itself). Cheaper, but only matches whole segments.
+    Segments,
+}
+
+/// Companion to [`PrefixMatch`] for suffix and "contains" queries, useful for
+/// searching qualified names like `file::name` by typing `name`. Alongside
+/// the original strings, [`SuffixMatch::insert`] indexes suffixes of each
+/// string into a second sorted vector of `(suffix, original_index)` pairs, so
+/// the same binary-search approach [`PrefixMatch::find`] uses can answer
+/// queries from the other end of the string.
+#[derive(Default, Debug)]
+pub struct SuffixMatch<Metadata> {
+    items: Vec<(String, Metadata)>,
+    suffixes: Vec<(String, usize)>,
+}
+
+impl<Metadata> SuffixMatch<Metadata> {
+    /// Insert an item, doesn't ensure the suffix index is sorted. `mode`
+    /// controls how many of the item's suffixes get indexed.
+    pub fn insert<S: Into<String>>(&mut self, s: S, meta: Metadata, mode: SuffixMode) {
+        let s = s.into();
+        let index = self.items.len();
+
+        match mode {
+            SuffixMode::All => {
+                for (i, _) in s.char_indices() {
+                    self.suffixes.push((s[i..].to_string(), index));
+                }
+            }
+            SuffixMode::Segments => {
+                self.suffixes.push((s.clone(), index));
+
+                let bytes = s.as_bytes();
+                let mut i = 0;
+                while i < bytes.len() {
+                    if bytes[i] == b'_' {
+                        self.suffixes.push((s[i + 1..].to_string(), index));
+                        i += 1;
+                    } else if s[i..].starts_with("::") {
+                        self.suffixes.push((s[i + 2..].to_string(), index));
+                        i += 2;
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+        }
+
+        self.items.push((s, meta));
+    }
+
+    /// Sorts the suffix index to allow for searching.
+    pub fn reorder(&mut self) {
+        self.suffixes.sort_unstable_by(|a, b| sort_cmp(&a.0, &b.0));
+    }
+
+    /// Find items containing `query` anywhere a suffix was indexed for (the
+    /// start of the string and of each `::`/`_`-delimited segment in
+    /// [`SuffixMode::Segments`] mode, or any position in [`SuffixMode::All`]
+    /// mode). Must call [`SuffixMatch::reorder`] first.
+    pub fn find_contains(&self, query: &str) -> Vec<(&str, &Metadata)> {
+        self.resolve(self.suffix_range(query), |_| true)
+    }
+
+    /// Find items that end with `query` exactly, where `query` starts at a
+    /// position a suffix was indexed for (in [`SuffixMode::Segments`] mode,
+    /// only whole segments). Must call [`SuffixMatch::reorder`] before calling this.
+    pub fn find_suffix(&self, query: &str) -> Vec<(&str, &Metadata)> {
+        self.resolve(self.suffix_range(query), |suffix| suffix.len() == query.len())
+    }
+
+    /// Mirrors [`PrefixMatch::find`]'s range-finding logic over the suffix
+    /// index, counting only entries that start with `query` as a hit.
+    fn suffix_range(&self, query: &str) -> Range<usize> {
+        let Ok(mid) = self.suffixes.binary_search_by(|(s, _)| {
+            if s.starts_with(query) { Ordering::Equal } else { s.as_str().cmp(query) }
+        }) else {
+            return 0..0;
+        };
+
+        let mut start = mid;
+        while start > 0 && self.suffixes[start - 1].0.starts_with(query) {
+            start -= 1;
+        }
+
+        let mut end = mid;
+        while end + 1 < self.suffixes.len() && self.suffixes[end + 1].0.starts_with(query) {
+            end += 1;
+        }
+
+        start..end + 1
+    }
+
+    /// Maps a range of the suffix index back to the original items it
+    /// points to, deduplicating entries that share an item and keeping only
+    /// those whose matched suffix passes `keep`.
+    fn resolve(&self, range: Range<usize>, keep: impl Fn(&str) -> bool) -> Vec<(&str, &Metadata)> {
+        let mut indices: Vec<usize> = self.suffixes[range]
+            .iter()
+            .filter(|(suffix, _)| keep(suffix))
+            .map(|&(_, index)| index)
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        indices.into_iter().map(|i| (self.items[i].0.as_str(), &self.items[i].1)).collect()
+    }
+}
+
+/// Storage mechanism for [`PrefixMatch::find_fuzzy`]. Unlike [`Match`], results
+/// aren't necessarily contiguous in sorted order, so each match is kept as an
+/// explicit index paired with its edit distance from the query.
+pub struct FuzzyMatch {
+    matches: Vec<(usize, u8)>,
+}
+
+impl FuzzyMatch {
+    /// Iterate through all items that match, together with their edit distance from the query.
+    pub fn iter<'a, 's: 'a, Metadata>(
+        &'a self,
+        tree: &'s PrefixMatch<Metadata>,
+    ) -> impl Iterator<Item = (&'s str, &'s Metadata, u8)> + 'a {
+        self.matches.iter().map(move |&(i, distance)| {
+            let (s, meta) = &tree.items[i];
+            (s.as_str(), meta, distance)
+        })
+    }
+}
+
+/// A single node in a [`TrieMatch`]: an optional value for the key spelled
+/// out by the path from the root, plus the edges to its children.
+#[derive(Debug)]
+struct TrieNode<Metadata> {
+    children: Vec<(char, TrieNode<Metadata>)>,
+    value: Option<Metadata>,
+}
+
+impl<Metadata> Default for TrieNode<Metadata> {
+    fn default() -> Self {
+        TrieNode { children: Vec::new(), value: None }
+    }
+}
+
+impl<Metadata> TrieNode<Metadata> {
+    fn child(&self, c: char) -> Option<&TrieNode<Metadata>> {
+        self.children.iter().find(|(ch, _)| *ch == c).map(|(_, node)| node)
+    }
+
+    fn child_mut(&mut self, c: char) -> &mut TrieNode<Metadata> {
+        if let Some(i) = self.children.iter().position(|(ch, _)| *ch == c) {
+            return &mut self.children[i].1;
+        }
+
+        self.children.push((c, TrieNode::default()));
+        &mut self.children.last_mut().unwrap().1
+    }
+}
+
+/// Trie-backed alternative to [`PrefixMatch`] for callers that need
+/// incremental inserts: keys share storage for common prefixes (e.g. `file`,
+/// `file_name`, `file::name` only pay for `file` once), and inserting a key
+/// is `O(key length)` with no [`PrefixMatch::reorder`]-equivalent step needed
+/// before [`TrieMatch::find`] can be called.
+#[derive(Default, Debug)]
+pub struct TrieMatch<Metadata> {
+    root: TrieNode<Metadata>,
+}
+
+impl<Metadata> TrieMatch<Metadata> {
+    /// Insert an item, creating any nodes missing along `s`'s path.
+    pub fn insert<S: Into<String>>(&mut self, s: S, meta: Metadata) {
+        let mut node = &mut self.root;
+        for c in s.into().chars() {
+            node = node.child_mut(c);
+        }
+        node.value = Some(meta);
+    }
+
+    /// Find all items whose key starts with `prefix`, returning an empty
+    /// iterator if no key follows that path.
+    pub fn find(&self, prefix: &str) -> TrieIter<'_, Metadata> {
+        let mut node = &self.root;
+        for c in prefix.chars() {
+            match node.child(c) {
+                Some(next) => node = next,
+                None => return TrieIter { stack: Vec::new() },
+            }
+        }
+
+        TrieIter { stack: vec![(prefix.to_string(), node)] }
+    }
+}
+
+/// Depth-first iterator over the items at and under a [`TrieMatch`] node,
+/// reconstructing each full key by accumulating edge labels along a stack of
+/// `(key, node)` pairs as it descends.
+pub struct TrieIter<'s, Metadata> {
+    stack: Vec<(String, &'s TrieNode<Metadata>)>,
+}
+
+impl<'s, Metadata> Iterator for TrieIter<'s, Metadata> {
+    type Item = (String, &'s Metadata);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((key, node)) = self.stack.pop() {
+            for (c, child) in &node.children {
+                let mut child_key = key.clone();
+                child_key.push(*c);
+                self.stack.push((child_key, child));
+            }
+
+            if let Some(meta) = &node.value {
+                return Some((key, meta));
+            }
+        }
+
+        None
+    }
 }
 
 #[cfg(test)]
@@ -126,4 +525,111 @@ mod test {
             assert_eq!(*x.1, y.1, "Mismatched metadata");
         }
     }
+
+    #[test]
+    fn find_with_spans() {
+        let mut tree = PrefixMatch::default();
+        tree.insert("file::name", 0);
+        tree.reorder();
+
+        let matches = tree.find("file::");
+        let (s, _, spans) = matches.iter_with_spans(&tree).next().unwrap();
+        assert_eq!(s, "file::name");
+        assert_eq!(spans.len(), 1, "Mismatched span count");
+        assert_eq!(spans[0], 0..6);
+        assert_eq!(&s[spans[0].clone()], "file::");
+    }
+
+    #[test]
+    fn find_fuzzy() {
+        let mut tree = PrefixMatch::default();
+        tree.insert("file", 0);
+        tree.insert("file_name", 1);
+        tree.insert("file::name", 2);
+        tree.insert("file::no", 3);
+        tree.reorder();
+
+        let matches = tree.find_fuzzy("fiel", 1);
+        let mut found: Vec<_> = matches.iter(&tree).map(|(s, meta, dist)| (s, *meta, dist)).collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [("file", 0, 1), ("file::name", 2, 1), ("file::no", 3, 1), ("file_name", 1, 1)]
+        );
+
+        // Too many errors for the bound.
+        assert_eq!(tree.find_fuzzy("xyz", 1).iter(&tree).count(), 0);
+    }
+
+    #[test]
+    fn rank() {
+        let mut tree = PrefixMatch::default();
+        tree.insert("file_name", 0);
+        tree.insert("file::name", 1);
+        tree.insert("something_else", 2);
+        tree.reorder();
+
+        let results = tree.rank("fnm");
+        let names: Vec<&str> = results.iter().map(|(s, _, _)| *s).collect();
+        assert_eq!(names, ["file_name", "file::name"], "Unmatched items shouldn't be returned");
+
+        // An exact, contiguous match should outrank a scattered subsequence match.
+        tree.insert("fi_le_na_me", 3);
+        tree.reorder();
+        let results = tree.rank("file_name");
+        assert_eq!(results[0].0, "file_name");
+        assert_eq!(results[1].0, "fi_le_na_me");
+    }
+
+    #[test]
+    fn suffix_match_contains() {
+        let mut tree = SuffixMatch::default();
+        tree.insert("file::name", 0, SuffixMode::All);
+        tree.insert("file::no", 1, SuffixMode::All);
+        tree.insert("other", 2, SuffixMode::All);
+        tree.reorder();
+
+        let mut found = tree.find_contains("name");
+        found.sort();
+        assert_eq!(found, [("file::name", &0)]);
+
+        let mut found = tree.find_contains("::n");
+        found.sort();
+        assert_eq!(found, [("file::name", &0), ("file::no", &1)]);
+    }
+
+    #[test]
+    fn suffix_match_exact_suffix() {
+        let mut tree = SuffixMatch::default();
+        tree.insert("file::name", 0, SuffixMode::Segments);
+        tree.insert("file_name", 1, SuffixMode::Segments);
+        tree.reorder();
+
+        // "name" is a whole segment in both keys.
+        let mut found = tree.find_suffix("name");
+        found.sort();
+        assert_eq!(found, [("file::name", &0), ("file_name", &1)]);
+
+        // Segments mode doesn't index suffixes that start mid-segment.
+        assert_eq!(tree.find_contains("ame"), []);
+    }
+
+    #[test]
+    fn trie_find() {
+        let mut trie = TrieMatch::default();
+        trie.insert("file", 0);
+        trie.insert("file_name", 1);
+        trie.insert("file::name", 2);
+        trie.insert("file::no", 3);
+
+        let mut found: Vec<_> = trie.find("file::").map(|(s, meta)| (s, *meta)).collect();
+        found.sort();
+        assert_eq!(found, [("file::name".to_string(), 2), ("file::no".to_string(), 3)]);
+
+        // The inserted key itself is included when it's also a valid prefix of others.
+        assert_eq!(trie.find("file").count(), 4);
+
+        // No stored key follows this path.
+        assert_eq!(trie.find("nope").count(), 0);
+    }
 }